@@ -2,99 +2,534 @@ use goblin::elf::{Elf};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::collections::HashSet;
-use std::error::Error;
+use std::collections::{HashMap, HashSet};
 use regex::Regex;
 use clap::{Parser};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The built-in ignore patterns applied when no config replaces them.
+fn default_ignore() -> Vec<String> {
+    vec![
+        r"ld-linux-x86-64.so.*".to_string(),
+        r"linux-vdso.so.*".to_string(),
+        r"libc.so.*".to_string(),
+    ]
+}
+
+/// Reusable bundling profile loaded from `--config <file.toml>`.
+#[derive(Debug, Default, Deserialize)]
+struct Config {
+    /// Regex patterns for libraries that should not be copied.
+    #[serde(default)]
+    ignore: Vec<String>,
+
+    /// Additional directories to search for libraries.
+    #[serde(default)]
+    search: Vec<String>,
+
+    /// When true, `ignore` extends the built-in defaults instead of replacing them.
+    #[serde(default)]
+    extend_default: bool,
 
-fn find_library_path(lib_name: &str, mut user_path: Vec<&str>) -> Option<PathBuf> {
-    // Check the system's library search paths (e.g., /lib, /usr/lib, etc.)
-    let mut system_paths = vec!["/lib", "/usr/lib", "/lib64", "/usr/lib64", "/usr/local/lib"];
-    if user_path.len() > 0 {
-        system_paths.append(&mut user_path);
+    /// Optional per-OS sections (e.g. `[linux]`) merged on top of the base.
+    #[serde(flatten, default)]
+    os: HashMap<String, OsConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OsConfig {
+    #[serde(default)]
+    ignore: Vec<String>,
+    #[serde(default)]
+    search: Vec<String>,
+}
+
+impl Config {
+    fn load(path: &str) -> Result<Config, Box<dyn std::error::Error>> {
+        let text = fs::read_to_string(path)?;
+        Ok(toml::from_str(&text)?)
     }
-    for path in system_paths {
-        let lib_path = Path::new(path).join(lib_name);
-        if lib_path.exists() {
-            return Some(lib_path);
+
+    /// Base `ignore` plus the section matching the current OS, if any.
+    fn merged_ignore(&self) -> Vec<String> {
+        let mut v = self.ignore.clone();
+        if let Some(os) = self.os.get(std::env::consts::OS) {
+            v.extend(os.ignore.clone());
         }
+        v
     }
 
-    // Check LD_LIBRARY_PATH environment variable
-    if let Ok(ld_path) = env::var("LD_LIBRARY_PATH") {
-        for path in ld_path.split(':') {
-            let lib_path = Path::new(path).join(lib_name);
-            if lib_path.exists() {
-                return Some(lib_path);
+    /// Base `search` plus the section matching the current OS, if any.
+    fn merged_search(&self) -> Vec<String> {
+        let mut v = self.search.clone();
+        if let Some(os) = self.os.get(std::env::consts::OS) {
+            v.extend(os.search.clone());
+        }
+        v
+    }
+
+    /// Resolve the effective ignore regexes, honoring `extend_default`.
+    fn ignore_patterns(&self) -> Result<Vec<Regex>, Box<dyn std::error::Error>> {
+        let configured = self.merged_ignore();
+        let patterns = if configured.is_empty() {
+            default_ignore()
+        } else if self.extend_default {
+            let mut d = default_ignore();
+            d.extend(configured);
+            d
+        } else {
+            configured
+        };
+        patterns.iter().map(|p| Ok(Regex::new(p)?)).collect()
+    }
+}
+
+/// Architecture of the target executable, used to reject incompatible
+/// candidate libraries (wrong class or machine) on multilib hosts.
+#[derive(Clone, Copy)]
+struct ElfArch {
+    is_64: bool,
+    little_endian: bool,
+    machine: u16,
+}
+
+impl ElfArch {
+    /// A candidate library is usable only if its class, endianness and
+    /// machine all match the target executable.
+    fn compatible_with(&self, other: &ElfArch) -> bool {
+        self.is_64 == other.is_64
+            && self.little_endian == other.little_endian
+            && self.machine == other.machine
+    }
+}
+
+/// Read the ELF identification header of `data`, returning `None` when the
+/// bytes are not a parseable ELF file.
+fn elf_arch(data: &[u8]) -> Option<ElfArch> {
+    let elf = Elf::parse(data).ok()?;
+    Some(ElfArch {
+        is_64: elf.is_64,
+        little_endian: elf.little_endian,
+        machine: elf.header.e_machine,
+    })
+}
+
+/// Build an [`ElfArch`] from a user-supplied `--arch` name for cross-bundling.
+fn arch_from_name(name: &str) -> Option<ElfArch> {
+    use goblin::elf::header;
+    let (is_64, machine) = match name {
+        "x86_64" => (true, header::EM_X86_64),
+        "aarch64" => (true, header::EM_AARCH64),
+        "i386" | "i686" => (false, header::EM_386),
+        "arm" => (false, header::EM_ARM),
+        _ => return None,
+    };
+    Some(ElfArch { is_64, little_endian: true, machine })
+}
+
+/// Map an ELF `e_machine` value to the name used by the `$PLATFORM` token.
+fn platform_name(machine: u16) -> &'static str {
+    use goblin::elf::header;
+    match machine {
+        header::EM_X86_64 => "x86_64",
+        header::EM_386 => "i386",
+        header::EM_AARCH64 => "aarch64",
+        header::EM_ARM => "arm",
+        _ => "unknown",
+    }
+}
+
+/// Expand the loader's dynamic-string tokens inside a single rpath entry.
+///
+/// `$ORIGIN` (and `${ORIGIN}`) becomes the directory of the ELF that declared
+/// the entry, `$LIB` the class-specific lib directory and `$PLATFORM` the
+/// machine name.
+fn expand_dynamic_tokens(entry: &str, origin: &Path, lib: &str, platform: &str) -> String {
+    let origin = origin.display().to_string();
+    entry
+        .replace("${ORIGIN}", &origin)
+        .replace("$ORIGIN", &origin)
+        .replace("${LIB}", lib)
+        .replace("$LIB", lib)
+        .replace("${PLATFORM}", platform)
+        .replace("$PLATFORM", platform)
+}
+
+/// Split the colon-separated rpath strings of `elf` and expand every token,
+/// using `origin` (the declaring binary's directory) for `$ORIGIN`.
+fn expand_search_dirs(entries: &[&str], origin: &Path, elf: &Elf) -> Vec<String> {
+    let lib = if elf.is_64 { "lib64" } else { "lib" };
+    let platform = platform_name(elf.header.e_machine);
+    let mut out = Vec::new();
+    for entry in entries {
+        for part in entry.split(':') {
+            if part.is_empty() {
+                continue;
             }
+            out.push(expand_dynamic_tokens(part, origin, lib, platform));
         }
     }
+    out
+}
 
-    None
+/// Read a little/native-endian `u32` at `off`, if in bounds.
+fn read_u32(data: &[u8], off: usize) -> Option<u32> {
+    let b = data.get(off..off + 4)?;
+    Some(u32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Read the NUL-terminated string starting at `off` in the cache's string section.
+fn read_cstr(data: &[u8], off: usize) -> Option<String> {
+    let bytes = data.get(off..)?;
+    let end = bytes.iter().position(|&b| b == 0)?;
+    Some(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+/// Parse the new-format `glibc-ld.so.cache1.1` blob into a soname → candidate
+/// paths map. Returns `None` for an absent magic or unrecognized version so
+/// the caller can degrade to directory scanning.
+fn parse_ld_so_cache(data: &[u8]) -> Option<HashMap<String, Vec<PathBuf>>> {
+    // struct cache_file_new: magic[17], version[3], nlibs(u32), len_strings(u32),
+    // flags(u8), pad[3], extension_offset(u32), unused[3] => 48-byte header,
+    // followed by `nlibs` 24-byte entries: flags(i32), key(u32), value(u32),
+    // osversion(u32), hwcap(u64). key/value are offsets from the file start.
+    const HEADER_SIZE: usize = 48;
+    const ENTRY_SIZE: usize = 24;
+
+    if data.len() < HEADER_SIZE {
+        return None;
+    }
+    if &data[0..17] != b"glibc-ld.so.cache" || &data[17..20] != b"1.1" {
+        return None;
+    }
+
+    let nlibs = read_u32(data, 20)? as usize;
+    let mut map: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for i in 0..nlibs {
+        let base = HEADER_SIZE + i * ENTRY_SIZE;
+        if base + ENTRY_SIZE > data.len() {
+            break;
+        }
+        let key = read_u32(data, base + 4)? as usize;
+        let value = read_u32(data, base + 8)? as usize;
+        if let (Some(soname), Some(path)) = (read_cstr(data, key), read_cstr(data, value)) {
+            map.entry(soname).or_default().push(PathBuf::from(path));
+        }
+    }
+    Some(map)
 }
 
-fn extract_dependencies(elf_data: &[u8]) -> Vec<String> {
-    let elf = Elf::parse(elf_data).expect("Failed to parse ELF file");
-    elf.libraries.iter().map(|lib| lib.to_string()).collect()
+/// Memory-map `/etc/ld.so.cache` (or `path`) and parse it, returning `None`
+/// when the file is missing or unparseable.
+fn load_ld_so_cache(path: &str) -> Option<HashMap<String, Vec<PathBuf>>> {
+    let file = fs::File::open(path).ok()?;
+    let mmap = unsafe { memmap2::Mmap::map(&file).ok()? };
+    parse_ld_so_cache(&mmap)
+}
+
+fn find_library_path(
+    lib_name: &str,
+    user_path: &[String],
+    rpaths: &[String],
+    runpaths: &[String],
+    arch: &ElfArch,
+    cache: Option<&HashMap<String, Vec<PathBuf>>>,
+) -> Option<PathBuf> {
+    let mut rejected: Vec<PathBuf> = Vec::new();
+
+    // Accept a candidate only if its ELF class/machine match the target.
+    let check = |lib_path: PathBuf, rejected: &mut Vec<PathBuf>| -> Option<PathBuf> {
+        if !lib_path.exists() {
+            return None;
+        }
+        match fs::read(&lib_path).ok().and_then(|data| elf_arch(&data)) {
+            Some(candidate) if arch.compatible_with(&candidate) => Some(lib_path),
+            _ => {
+                rejected.push(lib_path);
+                None
+            }
+        }
+    };
+
+    // Loader precedence: DT_RPATH, LD_LIBRARY_PATH, DT_RUNPATH.
+    let mut dirs: Vec<String> = Vec::new();
+    dirs.extend(rpaths.iter().cloned());
+    if let Ok(ld_path) = env::var("LD_LIBRARY_PATH") {
+        dirs.extend(ld_path.split(':').map(|p| p.to_string()));
+    }
+    dirs.extend(runpaths.iter().cloned());
+    for dir in &dirs {
+        if let Some(found) = check(Path::new(dir).join(lib_name), &mut rejected) {
+            return Some(found);
+        }
+    }
+
+    // Then the compiled loader cache, before scanning the default dirs.
+    if let Some(cache) = cache {
+        if let Some(paths) = cache.get(lib_name) {
+            for path in paths {
+                if let Some(found) = check(path.clone(), &mut rejected) {
+                    return Some(found);
+                }
+            }
+        }
+    }
+
+    // Finally the default system dirs plus any user `--search` entries.
+    let mut sys: Vec<String> = ["/lib", "/usr/lib", "/lib64", "/usr/lib64", "/usr/local/lib"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    sys.extend(user_path.iter().cloned());
+    for dir in &sys {
+        if let Some(found) = check(Path::new(dir).join(lib_name), &mut rejected) {
+            return Some(found);
+        }
+    }
+
+    if !rejected.is_empty() {
+        eprintln!(
+            "Warning: no architecture-compatible '{}' found; rejected {} incompatible candidate(s):",
+            lib_name,
+            rejected.len()
+        );
+        for path in &rejected {
+            eprintln!("  {}", path.display());
+        }
+    }
+
+    None
 }
 
 fn resolve_dependencies_recursively(
     executable_path: &str,
+    user_search: &[String],
+    arch: &ElfArch,
+    cache: Option<&HashMap<String, Vec<PathBuf>>>,
     processed: &mut HashSet<String>,
-) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
-    // Avoid processing the same library multiple times
-    if processed.contains(executable_path) {
-        return Ok(processed.clone());
+    resolved: &mut HashMap<String, PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Avoid processing the same file multiple times
+    if !processed.insert(executable_path.to_string()) {
+        return Ok(());
     }
 
     let elf_data = fs::read(executable_path)?;
-    let dependencies = extract_dependencies(&elf_data);
-
-    for dep in dependencies {
-        if !processed.contains(&dep) {
-            processed.insert(dep.clone());
-            // Recursively resolve dependencies of the found library
-            resolve_dependencies_recursively(&dep, processed)?;
+    let elf = Elf::parse(&elf_data)?;
+
+    // rpaths are relative to the directory of the binary that declared them.
+    let origin = Path::new(executable_path)
+        .parent()
+        .unwrap_or_else(|| Path::new("."));
+    let rpaths = expand_search_dirs(&elf.rpaths, origin, &elf);
+    let runpaths = expand_search_dirs(&elf.runpaths, origin, &elf);
+
+    for dep in elf.libraries.iter() {
+        let dep = dep.to_string();
+        if resolved.contains_key(&dep) {
+            continue;
+        }
+        match find_library_path(&dep, user_search, &rpaths, &runpaths, arch, cache) {
+            Some(dep_path) => {
+                // Recurse into the resolved file so its own `$ORIGIN` is used.
+                resolve_dependencies_recursively(
+                    dep_path.to_str().unwrap(),
+                    user_search,
+                    arch,
+                    cache,
+                    processed,
+                    resolved,
+                )?;
+                resolved.insert(dep, dep_path);
+            }
+            None => {
+                println!("Library {} not found", dep);
+            }
         }
     }
 
-    Ok(processed.clone())
+    Ok(())
+}
+
+/// A single record of what landed in the bundle, written to `manifest.toml`.
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    soname: String,
+    source: String,
+    dest: String,
+    sha256: String,
 }
 
-fn copy_libraries(libraries: &HashSet<String>, target_dir: &str, search_path: Vec<&str>) -> Result<(), Box<dyn std::error::Error>> {
-    for lib in libraries {
-        if let Some(dep_path) = find_library_path(lib, search_path.clone()) {
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    library: Vec<ManifestEntry>,
+}
+
+/// The real library file plus the `(link_name, target_name)` pairs describing
+/// the soname symlinks that point at it.
+type SymlinkChain = (PathBuf, Vec<(String, String)>);
+
+/// Resolve the real target of `dep_path` and collect every symlink in its
+/// source directory that points (directly or transitively) at that target.
+///
+/// Returns the real file and a list of `(link_name, target_name)` pairs
+/// describing the one-hop links to recreate inside `libs/`.
+fn symlink_chain(dep_path: &Path) -> Result<SymlinkChain, Box<dyn std::error::Error>> {
+    let real = fs::canonicalize(dep_path)?;
+    let dir = real.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut links: Vec<(String, String)> = Vec::new();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let meta = fs::symlink_metadata(&path)?;
+            if !meta.file_type().is_symlink() {
+                continue;
+            }
+            // Only links that ultimately resolve to the same real file.
+            if fs::canonicalize(&path).ok().as_deref() != Some(real.as_path()) {
+                continue;
+            }
+            let link_name = path.file_name().unwrap().to_string_lossy().to_string();
+            let target = fs::read_link(&path)?;
+            let target_name = target.file_name().unwrap().to_string_lossy().to_string();
+            links.push((link_name, target_name));
+        }
+    }
+
+    Ok((real, links))
+}
 
-            // Copy the library to the target directory
-            let target_path = Path::new(target_dir).join(dep_path.file_name().unwrap());
-            println!("{} => {}", dep_path.display(), target_path.display());
-            fs::copy(dep_path, target_path)?;
+/// Hex-encoded SHA-256 of the file at `path`.
+fn sha256_file(path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let data = fs::read(path)?;
+    let mut hasher = Sha256::new();
+    hasher.update(&data);
+    Ok(format!("{:x}", hasher.finalize()))
+}
 
+/// Copy the resolved libraries into `target_dir`, recording a deterministic
+/// SHA-256 `manifest.toml`. A library whose destination already exists with a
+/// matching digest is skipped, making repeated runs idempotent. Returns the
+/// `(copied, skipped)` counts.
+///
+/// When `preserve_symlinks` is set, the real target of a versioned library is
+/// copied once and the intermediate soname symlinks are recreated inside
+/// `target_dir`; otherwise the resolved file is flattened into a single copy.
+fn copy_libraries(
+    libraries: &HashMap<String, PathBuf>,
+    target_dir: &str,
+    preserve_symlinks: bool,
+) -> Result<(usize, usize), Box<dyn std::error::Error>> {
+    let manifest_path = Path::new(target_dir).join("manifest.toml");
+
+    // Load the previous manifest (if any) to decide what can be skipped.
+    let previous: HashMap<String, String> = if manifest_path.exists() {
+        let text = fs::read_to_string(&manifest_path)?;
+        let manifest: Manifest = toml::from_str(&text)?;
+        manifest
+            .library
+            .into_iter()
+            .map(|e| (e.dest, e.sha256))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    // Sort by soname so the manifest is stable across runs.
+    let mut libs: Vec<(&String, &PathBuf)> = libraries.iter().collect();
+    libs.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut entries: Vec<ManifestEntry> = Vec::new();
+    let mut copied = 0;
+    let mut skipped = 0;
+    for (soname, dep_path) in libs {
+        // With symlink preservation the real target is the copy source and the
+        // versioned aliases are recreated as links; otherwise flatten.
+        let (source, links) = if preserve_symlinks {
+            symlink_chain(dep_path)?
+        } else {
+            (dep_path.clone(), Vec::new())
+        };
+
+        let digest = sha256_file(&source)?;
+        let dest_name = source.file_name().unwrap().to_string_lossy().to_string();
+        let target_path = Path::new(target_dir).join(&dest_name);
+
+        // Skip only if the manifest digest matches the source and the file
+        // already in the bundle still hashes to that same digest.
+        if target_path.exists()
+            && previous.get(&dest_name) == Some(&digest)
+            && sha256_file(&target_path).ok().as_ref() == Some(&digest)
+        {
+            println!("skip {} (unchanged)", target_path.display());
+            skipped += 1;
         } else {
-            println!("Library {} not found", lib);
+            println!("{} => {}", source.display(), target_path.display());
+            fs::copy(&source, &target_path)?;
+            copied += 1;
         }
+
+        // Recreate the soname symlink chain alongside the real file.
+        for (link_name, link_target) in &links {
+            if link_name == &dest_name {
+                continue;
+            }
+            let link_path = Path::new(target_dir).join(link_name);
+            if fs::symlink_metadata(&link_path).is_ok() {
+                fs::remove_file(&link_path)?;
+            }
+            std::os::unix::fs::symlink(link_target, &link_path)?;
+            println!("link {} -> {}", link_path.display(), link_target);
+        }
+
+        entries.push(ManifestEntry {
+            soname: soname.clone(),
+            source: source.display().to_string(),
+            dest: dest_name,
+            sha256: digest,
+        });
     }
 
-    Ok(())
+    let manifest = Manifest { library: entries };
+    fs::write(&manifest_path, toml::to_string(&manifest)?)?;
+
+    Ok((copied, skipped))
 }
 
-fn resolve_dependencies(executable_path: &str) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
-    let mut processed = HashSet::new();
-    let mut all_dependencies = resolve_dependencies_recursively(executable_path, &mut processed)?;
+fn resolve_dependencies(
+    executable_path: &str,
+    user_search: &[String],
+    arch: &ElfArch,
+    ignore_patterns: &[Regex],
+    use_ld_cache: bool,
+) -> Result<HashMap<String, PathBuf>, Box<dyn std::error::Error>> {
+    // Consult the compiled loader cache for libraries in nonstandard dirs.
+    let cache = if use_ld_cache {
+        load_ld_so_cache("/etc/ld.so.cache")
+    } else {
+        None
+    };
 
-    // Define the ignore list patterns
-    let ignore_patterns = vec![
-        Regex::new(r"ld-linux-x86-64.so.*").unwrap(),
-        Regex::new(r"linux-vdso.so.*").unwrap(),
-        Regex::new(r"libc.so.*").unwrap(),
-    ];
+    let mut processed = HashSet::new();
+    let mut resolved = HashMap::new();
+    resolve_dependencies_recursively(
+        executable_path,
+        user_search,
+        arch,
+        cache.as_ref(),
+        &mut processed,
+        &mut resolved,
+    )?;
 
     // Filter out dependencies that match the ignore list patterns
-    all_dependencies.retain(|dep| {
+    resolved.retain(|dep, _| {
         !ignore_patterns.iter().any(|regex| regex.is_match(dep))
     });
-    //println!("{:?}", all_dependencies);
-    Ok(all_dependencies)
+    //println!("{:?}", resolved);
+    Ok(resolved)
 }
 
 // 命令行参数
@@ -113,6 +548,22 @@ struct Args {
     #[clap(short, long)]
     search: Option<String>,
 
+    /// override the target architecture for cross-bundling (e.g. x86_64, aarch64)
+    #[clap(long)]
+    arch: Option<String>,
+
+    /// TOML profile with ignore patterns and search paths
+    #[clap(short, long)]
+    config: Option<String>,
+
+    /// flatten versioned libraries instead of recreating soname symlink chains
+    #[clap(long)]
+    no_symlinks: bool,
+
+    /// resolve libraries through /etc/ld.so.cache (on by default for native bundling)
+    #[clap(long, action = clap::ArgAction::Set, default_value_t = true)]
+    use_ld_cache: bool,
+
 }
 
 fn create_target_dirs(target_dir: &str) {
@@ -178,14 +629,52 @@ fn main() {
     fs::copy(executable_path, Path::new(target_dir)
         .join(exe_filename).to_str().unwrap()).expect("Failed to copy executable");
 
-    let mut user_search_path = Vec::new();
-    let search_path_str = args.search.unwrap();
-    user_search_path.push(search_path_str.as_str());
-    match resolve_dependencies(executable_path) {
+    // Determine the architecture every copied library must match: either the
+    // `--arch` override or the class/machine of the target executable itself.
+    let arch = match &args.arch {
+        Some(name) => match arch_from_name(name) {
+            Some(a) => a,
+            None => {
+                eprintln!("Error: unknown --arch value '{}'", name);
+                std::process::exit(1);
+            }
+        },
+        None => {
+            let data = fs::read(executable_path).expect("Failed to read executable");
+            elf_arch(&data).expect("Failed to parse executable ELF header")
+        }
+    };
+
+    // Load the optional TOML profile and derive the effective ignore set.
+    let config = match &args.config {
+        Some(path) => match Config::load(path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error loading config '{}': {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => Config::default(),
+    };
+    let ignore_patterns = match config.ignore_patterns() {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Error in config ignore patterns: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Merge CLI `--search` entries with the config's search paths.
+    let mut user_search_path: Vec<String> = args.search.iter().cloned().collect();
+    user_search_path.extend(config.merged_search());
+
+    match resolve_dependencies(executable_path, &user_search_path, &arch, &ignore_patterns, args.use_ld_cache) {
         Ok(all_dependencies) => {
-            copy_libraries(&all_dependencies,
+            let (copied, skipped) = copy_libraries(&all_dependencies,
                            Path::new(target_dir).join("libs").to_str().unwrap(),
-                           user_search_path).expect("Failed to copy library");
+                           !args.no_symlinks)
+                .expect("Failed to copy library");
+            println!("{} copied, {} skipped", copied, skipped);
         }
         Err(err) => {
             eprintln!("Error: {}", err);